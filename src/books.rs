@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "server")]
 use sqlx::prelude::FromRow;
 
+use crate::validation::BookFormErrors;
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "server", derive(FromRow))]
 pub struct Book {
@@ -15,6 +17,124 @@ pub struct Book {
     pub isbn: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SortOrder {
+    TitleAsc,
+    TitleDesc,
+    AuthorAsc,
+    AuthorDesc,
+    PriceAsc,
+    PriceDesc,
+}
+
+impl SortOrder {
+    fn column(&self) -> &'static str {
+        match self {
+            SortOrder::TitleAsc | SortOrder::TitleDesc => "title",
+            SortOrder::AuthorAsc | SortOrder::AuthorDesc => "author",
+            SortOrder::PriceAsc | SortOrder::PriceDesc => "price",
+        }
+    }
+
+    fn direction(&self) -> &'static str {
+        match self {
+            SortOrder::TitleAsc | SortOrder::AuthorAsc | SortOrder::PriceAsc => "ASC",
+            SortOrder::TitleDesc | SortOrder::AuthorDesc | SortOrder::PriceDesc => "DESC",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BookPage {
+    pub books: Vec<Book>,
+    pub next_cursor: Option<String>,
+}
+
+// カーソルは "ソート値\u{1}id" という不透明な文字列として表現する
+fn encode_cursor(sort_value: &str, id: i32) -> String {
+    format!("{sort_value}\u{1}{id}")
+}
+
+fn decode_cursor(cursor: &str) -> Option<(String, i32)> {
+    let (sort_value, id) = cursor.split_once('\u{1}')?;
+    Some((sort_value.to_string(), id.parse().ok()?))
+}
+
+#[server]
+pub async fn load_books_page(
+    limit: u32,
+    cursor: Option<String>,
+    sort: SortOrder,
+    query: Option<String>,
+) -> Result<BookPage> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+
+    let column = sort.column();
+    let direction = sort.direction();
+    let op = if direction == "ASC" { ">" } else { "<" };
+
+    // 検索語がある場合はbooks_ftsと結合して絞り込む
+    let from_clause = if query.is_some() {
+        "FROM books JOIN books_fts ON books_fts.rowid = books.id"
+    } else {
+        "FROM books"
+    };
+
+    let mut conditions = Vec::new();
+    if query.is_some() {
+        conditions.push("books_fts MATCH ?".to_string());
+    }
+    if cursor.is_some() {
+        conditions.push(format!("(books.{column}, books.id) {op} (?, ?)"));
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    // limitより1件多く取得し、次ページの有無を判定する
+    let sql = format!(
+        r#"SELECT books.id, books.title, books.author, books.publisher, books.price, books.isbn
+           {from_clause}
+           {where_clause}
+           ORDER BY books.{column} {direction}, books.id {direction}
+           LIMIT ?"#
+    );
+
+    let mut sql_query = sqlx::query_as::<_, Book>(&sql);
+    if let Some(query) = &query {
+        sql_query = sql_query.bind(query);
+    }
+    if let Some(cursor) = &cursor {
+        let (sort_value, id) =
+            decode_cursor(cursor).ok_or_else(|| ServerFnError::ServerError("不正なカーソルです".to_string()))?;
+        sql_query = sql_query.bind(sort_value).bind(id);
+    }
+    let mut books = sql_query
+        .bind(limit as i64 + 1)
+        .fetch_all(db.pool())
+        .await?;
+
+    let next_cursor = if books.len() > limit as usize {
+        books.truncate(limit as usize);
+        books.last().map(|book| {
+            let sort_value = match sort {
+                SortOrder::TitleAsc | SortOrder::TitleDesc => book.title.clone(),
+                SortOrder::AuthorAsc | SortOrder::AuthorDesc => book.author.clone(),
+                SortOrder::PriceAsc | SortOrder::PriceDesc => book.price.to_string(),
+            };
+            encode_cursor(&sort_value, book.id)
+        })
+    } else {
+        None
+    };
+
+    Ok(BookPage { books, next_cursor })
+}
+
 #[server]
 pub async fn load_books(query: String) -> Result<Vec<Book>> {
     use crate::db::DB;
@@ -26,14 +146,238 @@ pub async fn load_books(query: String) -> Result<Vec<Book>> {
             .fetch_all(db.pool())
             .await?
     } else {
-        sqlx::query_as::<_, Book>(
-            r#"SELECT id, title, author, publisher, price, isbn FROM books WHERE title LIKE ? OR author LIKE ?"#,
-        )
-        .bind(format!("%{}%", query))
-        .bind(format!("%{}%", query))
-        .fetch_all(db.pool())
-        .await?
+        match run_fts_search(db.pool(), &query).await {
+            Ok(books) => books,
+            // FTS5のクエリ構文（コロンや括弧、先頭のNOT/-など）を壊す入力が来た場合、
+            // クオートされたフレーズとして扱い直し、入力語の並びに一致するフレーズ検索として実行する
+            Err(sqlx::Error::Database(db_error)) if is_fts5_syntax_error(db_error.as_ref()) => {
+                run_fts_search(db.pool(), &quote_fts5_phrase(&query)).await?
+            }
+            Err(e) => return Err(e.into()),
+        }
     };
 
     Ok(books)
 }
+
+#[cfg(feature = "server")]
+async fn run_fts_search(
+    pool: &sqlx::SqlitePool,
+    match_query: &str,
+) -> std::result::Result<Vec<Book>, sqlx::Error> {
+    sqlx::query_as::<_, Book>(
+        r#"
+        SELECT books.id, books.title, books.author, books.publisher, books.price, books.isbn
+        FROM books_fts
+        JOIN books ON books.id = books_fts.rowid
+        WHERE books_fts MATCH ?
+        ORDER BY rank
+        "#,
+    )
+    .bind(match_query)
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(feature = "server")]
+fn is_fts5_syntax_error(db_error: &(dyn sqlx::error::DatabaseError)) -> bool {
+    let message = db_error.message().to_lowercase();
+    // 列フィルタとして解釈された入力（"a:b"や"a-b"）は"no such column: ..."として報告される
+    message.contains("fts5") || message.contains("no such column")
+}
+
+// FTS5のクオート済みフレーズにするため、内部のダブルクオートを二重化して全体を囲む
+fn quote_fts5_phrase(query: &str) -> String {
+    format!("\"{}\"", query.replace('"', "\"\""))
+}
+
+#[server]
+pub async fn get_book(id: i32) -> Result<Option<Book>> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+    let book = sqlx::query_as::<_, Book>(
+        r#"SELECT id, title, author, publisher, price, isbn FROM books WHERE id = ?"#,
+    )
+    .bind(id)
+    .fetch_optional(db.pool())
+    .await?;
+
+    Ok(book)
+}
+
+#[server]
+pub async fn create_book(
+    title: String,
+    author: String,
+    publisher: String,
+    price: String,
+    isbn: String,
+) -> Result<std::result::Result<Book, BookFormErrors>> {
+    use crate::db::DB;
+    use crate::validation::validate_book_form;
+
+    let errors = validate_book_form(&title, &author, &publisher, &price, &isbn);
+    if errors.has_errors() {
+        return Ok(Err(errors));
+    }
+
+    let title = title.trim();
+    let author = author.trim();
+    let publisher = publisher.trim();
+    let isbn = isbn.trim();
+    let price: u32 = price.trim().parse().expect("price was validated above");
+
+    let db = DB::get().await;
+    let result = db
+        .execute(
+            sqlx::query(
+                r#"INSERT INTO books (title, author, publisher, price, isbn) VALUES (?, ?, ?, ?, ?)"#,
+            )
+            .bind(title)
+            .bind(author)
+            .bind(publisher)
+            .bind(price)
+            .bind(isbn),
+        )
+        .await?;
+
+    Ok(Ok(Book {
+        id: result.last_insert_rowid() as i32,
+        title: title.to_string(),
+        author: author.to_string(),
+        publisher: publisher.to_string(),
+        price,
+        isbn: isbn.to_string(),
+    }))
+}
+
+#[server]
+pub async fn update_book(
+    id: i32,
+    title: String,
+    author: String,
+    publisher: String,
+    price: String,
+    isbn: String,
+) -> Result<std::result::Result<Book, BookFormErrors>> {
+    use crate::db::DB;
+    use crate::validation::validate_book_form;
+
+    let errors = validate_book_form(&title, &author, &publisher, &price, &isbn);
+    if errors.has_errors() {
+        return Ok(Err(errors));
+    }
+
+    let title = title.trim();
+    let author = author.trim();
+    let publisher = publisher.trim();
+    let isbn = isbn.trim();
+    let price: u32 = price.trim().parse().expect("price was validated above");
+
+    let db = DB::get().await;
+    db.execute(
+        sqlx::query(
+            r#"UPDATE books SET title = ?, author = ?, publisher = ?, price = ?, isbn = ? WHERE id = ?"#,
+        )
+        .bind(title)
+        .bind(author)
+        .bind(publisher)
+        .bind(price)
+        .bind(isbn)
+        .bind(id),
+    )
+    .await?;
+
+    Ok(Ok(Book {
+        id,
+        title: title.to_string(),
+        author: author.to_string(),
+        publisher: publisher.to_string(),
+        price,
+        isbn: isbn.to_string(),
+    }))
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BookImportRow {
+    pub title: String,
+    pub author: String,
+    pub publisher: String,
+    pub price: String,
+    pub isbn: String,
+}
+
+#[server]
+pub async fn import_books(
+    rows: Vec<BookImportRow>,
+) -> Result<std::result::Result<Vec<Book>, std::collections::HashMap<usize, BookFormErrors>>> {
+    use crate::db::DB;
+    use crate::validation::validate_book_form;
+
+    // 1行目を1として、行番号をキーにエラーを集める
+    let mut row_errors = std::collections::HashMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        let errors = validate_book_form(&row.title, &row.author, &row.publisher, &row.price, &row.isbn);
+        if errors.has_errors() {
+            row_errors.insert(index + 1, errors);
+        }
+    }
+
+    if !row_errors.is_empty() {
+        return Ok(Err(row_errors));
+    }
+
+    let db = DB::get().await;
+    let mut transaction = db.begin().await?;
+
+    let mut inserted = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let title = row.title.trim();
+        let author = row.author.trim();
+        let publisher = row.publisher.trim();
+        let isbn = row.isbn.trim();
+        let price: u32 = row.price.trim().parse().expect("price was validated above");
+
+        let result = sqlx::query(
+            r#"INSERT INTO books (title, author, publisher, price, isbn) VALUES (?, ?, ?, ?, ?)"#,
+        )
+        .bind(title)
+        .bind(author)
+        .bind(publisher)
+        .bind(price)
+        .bind(isbn)
+        .execute(&mut *transaction)
+        .await;
+
+        match result {
+            Ok(query_result) => inserted.push(Book {
+                id: query_result.last_insert_rowid() as i32,
+                title: title.to_string(),
+                author: author.to_string(),
+                publisher: publisher.to_string(),
+                price,
+                isbn: isbn.to_string(),
+            }),
+            Err(e) => {
+                transaction.rollback().await?;
+                return Err(e.into());
+            }
+        }
+    }
+
+    transaction.commit().await?;
+
+    Ok(Ok(inserted))
+}
+
+#[server]
+pub async fn delete_book(id: i32) -> Result<()> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+    db.execute(sqlx::query(r#"DELETE FROM books WHERE id = ?"#).bind(id))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use sqlx::prelude::FromRow;
+
+use crate::books::Book;
+use crate::Route;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "server", derive(FromRow))]
+pub struct Category {
+    pub id: i32,
+    pub name: String,
+    pub parent_id: Option<i32>,
+}
+
+#[server]
+pub async fn create_category(name: String, parent_id: Option<i32>) -> Result<Category> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+    let name = name.trim();
+    let result = db
+        .execute(
+            sqlx::query(r#"INSERT INTO categories (name, parent_id) VALUES (?, ?)"#)
+                .bind(name)
+                .bind(parent_id),
+        )
+        .await?;
+
+    Ok(Category {
+        id: result.last_insert_rowid() as i32,
+        name: name.to_string(),
+        parent_id,
+    })
+}
+
+#[server]
+pub async fn list_categories() -> Result<Vec<Category>> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+    let categories = sqlx::query_as::<_, Category>(
+        r#"SELECT id, name, parent_id FROM categories ORDER BY name"#,
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(categories)
+}
+
+#[server]
+pub async fn assign_category(book_id: i32, category_id: i32) -> Result<()> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+    db.execute(
+        sqlx::query(
+            r#"INSERT OR IGNORE INTO book_categories (book_id, category_id) VALUES (?, ?)"#,
+        )
+        .bind(book_id)
+        .bind(category_id),
+    )
+    .await?;
+
+    Ok(())
+}
+
+// 指定したカテゴリの祖先をルートから順に辿った系譜を返す（自分自身を含む）
+#[server]
+pub async fn category_ancestors(category_id: i32) -> Result<Vec<Category>> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+    let ancestors = sqlx::query_as::<_, Category>(
+        r#"
+        WITH RECURSIVE ancestors(id, name, parent_id, depth) AS (
+            SELECT id, name, parent_id, 0 FROM categories WHERE id = ?
+            UNION ALL
+            SELECT c.id, c.name, c.parent_id, a.depth + 1
+            FROM categories c
+            JOIN ancestors a ON c.id = a.parent_id
+        )
+        SELECT id, name, parent_id FROM ancestors ORDER BY depth DESC
+        "#,
+    )
+    .bind(category_id)
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(ancestors)
+}
+
+#[server]
+pub async fn books_by_category(category_id: i32) -> Result<Vec<Book>> {
+    use crate::db::DB;
+
+    let db = DB::get().await;
+    let books = sqlx::query_as::<_, Book>(
+        r#"
+        SELECT books.id, books.title, books.author, books.publisher, books.price, books.isbn
+        FROM books
+        JOIN book_categories ON book_categories.book_id = books.id
+        WHERE book_categories.category_id = ?
+        "#,
+    )
+    .bind(category_id)
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(books)
+}
+
+#[component]
+pub fn Breadcrumb(chain: Vec<Category>) -> Element {
+    rsx! {
+        nav { class: "flex gap-2 text-sm text-gray-500",
+            for (index , category) in chain.iter().enumerate() {
+                if index > 0 {
+                    span { "/" }
+                }
+                Link { to: Route::CategoryBooks { id: category.id }, "{category.name}" }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn CategoryBooks(id: i32) -> Element {
+    let chain_resource = use_resource(move || async move { category_ancestors(id).await });
+    let books_resource = use_resource(move || async move { books_by_category(id).await });
+
+    let breadcrumb = match chain_resource.read().as_ref() {
+        Some(Ok(chain)) => rsx! {
+            Breadcrumb { chain: chain.clone() }
+        },
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            rsx! {}
+        }
+        None => rsx! {},
+    };
+
+    let content = match books_resource.read().as_ref() {
+        Some(Ok(books)) => rsx! {
+            crate::Books { books: books.clone() }
+        },
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            rsx! {
+                div { "Error: {e}" }
+            }
+        }
+        None => rsx! {
+            div { "loading..." }
+        },
+    };
+
+    rsx! {
+        div { class: "flex flex-col gap-6",
+            {breadcrumb}
+            {content}
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use sqlx::{
-    sqlite::{SqliteArguments, SqlitePoolOptions},
+    sqlite::{SqliteArguments, SqlitePoolOptions, SqliteQueryResult},
     Sqlite, SqlitePool, Transaction,
 };
 use std::time::Duration;
@@ -14,7 +14,6 @@ pub struct DBOption {
 
 pub struct DB {
     pool: SqlitePool,
-    transaction: Option<Transaction<'static, Sqlite>>,
 }
 
 impl DB {
@@ -26,10 +25,12 @@ impl DB {
             .await
             .expect("Cannot connect to database");
 
-        DB {
-            pool,
-            transaction: None,
-        }
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("Failed to run migrations");
+
+        DB { pool }
     }
 
     pub async fn get() -> &'static DB {
@@ -50,14 +51,16 @@ impl DB {
     }
 
     pub async fn execute<'a>(
-        &mut self,
+        &self,
         query: sqlx::query::Query<'a, Sqlite, SqliteArguments<'a>>,
-    ) -> Result<(), sqlx::error::Error> {
-        if let Some(ref mut t) = self.transaction {
-            query.execute(&mut **t).await?;
-        } else {
-            query.execute(&self.pool).await?;
-        }
-        Ok(())
+    ) -> Result<SqliteQueryResult, sqlx::error::Error> {
+        query.execute(&self.pool).await
+    }
+
+    // 呼び出し元が所有する、このリクエストだけのトランザクションを開始する。
+    // DBにトランザクションを保持させないことで、他のリクエストのexecuteが
+    // 無関係なトランザクションに巻き込まれないようにする。
+    pub async fn begin(&self) -> Result<Transaction<'static, Sqlite>, sqlx::error::Error> {
+        self.pool.begin().await
     }
 }
@@ -0,0 +1,127 @@
+use dioxus::prelude::*;
+
+use crate::books::{create_book, delete_book, get_book, update_book};
+use crate::validation::BookFormErrors;
+use crate::Route;
+
+#[component]
+pub fn NewBook() -> Element {
+    rsx! {
+        BookForm { id: None }
+    }
+}
+
+#[component]
+pub fn EditBook(id: i32) -> Element {
+    rsx! {
+        BookForm { id: Some(id) }
+    }
+}
+
+#[component]
+fn BookForm(id: Option<i32>) -> Element {
+    let navigator = use_navigator();
+    let mut title = use_signal(String::new);
+    let mut author = use_signal(String::new);
+    let mut publisher = use_signal(String::new);
+    let mut price = use_signal(String::new);
+    let mut isbn = use_signal(String::new);
+    let mut errors = use_signal(BookFormErrors::default);
+
+    let _existing_book = use_resource(move || async move {
+        if let Some(id) = id {
+            if let Ok(Some(book)) = get_book(id).await {
+                title.set(book.title);
+                author.set(book.author);
+                publisher.set(book.publisher);
+                price.set(book.price.to_string());
+                isbn.set(book.isbn);
+            }
+        }
+    });
+
+    let onsubmit = move |_| async move {
+        let result = if let Some(id) = id {
+            update_book(
+                id,
+                title(),
+                author(),
+                publisher(),
+                price(),
+                isbn(),
+            )
+            .await
+        } else {
+            create_book(title(), author(), publisher(), price(), isbn()).await
+        };
+
+        match result {
+            Ok(Ok(_)) => {
+                errors.set(BookFormErrors::default());
+                navigator.push(Route::Home {
+                    query: String::new(),
+                });
+            }
+            Ok(Err(form_errors)) => errors.set(form_errors),
+            Err(e) => eprintln!("{e}"),
+        }
+    };
+
+    let on_delete = move |_| async move {
+        if let Some(id) = id {
+            if let Err(e) = delete_book(id).await {
+                eprintln!("{e}");
+                return;
+            }
+            navigator.push(Route::Home {
+                query: String::new(),
+            });
+        }
+    };
+
+    rsx! {
+        form { class: "flex flex-col gap-4 max-w-md", onsubmit,
+            div {
+                label { "タイトル" }
+                input { class: "border-1", value: "{title}", oninput: move |e| title.set(e.value()) }
+                if let Some(message) = &errors().title {
+                    p { class: "text-red-600 text-sm", "{message}" }
+                }
+            }
+            div {
+                label { "著者" }
+                input { class: "border-1", value: "{author}", oninput: move |e| author.set(e.value()) }
+                if let Some(message) = &errors().author {
+                    p { class: "text-red-600 text-sm", "{message}" }
+                }
+            }
+            div {
+                label { "出版社" }
+                input { class: "border-1", value: "{publisher}", oninput: move |e| publisher.set(e.value()) }
+                if let Some(message) = &errors().publisher {
+                    p { class: "text-red-600 text-sm", "{message}" }
+                }
+            }
+            div {
+                label { "価格" }
+                input { class: "border-1", value: "{price}", oninput: move |e| price.set(e.value()) }
+                if let Some(message) = &errors().price {
+                    p { class: "text-red-600 text-sm", "{message}" }
+                }
+            }
+            div {
+                label { "ISBN" }
+                input { class: "border-1", value: "{isbn}", oninput: move |e| isbn.set(e.value()) }
+                if let Some(message) = &errors().isbn {
+                    p { class: "text-red-600 text-sm", "{message}" }
+                }
+            }
+            div { class: "flex gap-2",
+                button { r#type: "submit", class: "border-1 rounded px-4 py-2", "保存" }
+                if id.is_some() {
+                    button { r#type: "button", class: "border-1 rounded px-4 py-2", onclick: on_delete, "削除" }
+                }
+            }
+        }
+    }
+}
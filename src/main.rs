@@ -4,14 +4,27 @@ use dioxus::prelude::*;
 mod db;
 
 mod books;
+mod categories;
+mod forms;
+#[cfg(feature = "server")]
+mod opds;
+mod validation;
 
-use crate::books::{load_books, Book};
+use crate::books::{load_books, load_books_page, Book, SortOrder};
+use crate::categories::CategoryBooks;
+use crate::forms::{EditBook, NewBook};
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
 enum Route {
     #[route("/?:query")]
     Home {query: String},
+    #[route("/books/new")]
+    NewBook {},
+    #[route("/books/:id/edit")]
+    EditBook {id: i32},
+    #[route("/categories/:id")]
+    CategoryBooks {id: i32},
 }
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
@@ -20,9 +33,38 @@ const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
 fn main() {
     color_eyre::install().expect("Failed to install color_eyre");
+
+    #[cfg(feature = "server")]
+    {
+        tokio::runtime::Runtime::new()
+            .expect("Failed to start tokio runtime")
+            .block_on(serve());
+    }
+
+    #[cfg(not(feature = "server"))]
     dioxus::launch(App);
 }
 
+// `/opds` のようなDioxusのサーバー関数経由ではない生のHTTPルートを
+// Dioxusのルーターにマージして配信する
+#[cfg(feature = "server")]
+async fn serve() {
+    use axum::routing::get;
+    use dioxus::fullstack::prelude::*;
+
+    let router = axum::Router::new()
+        .route("/opds", get(opds::feed))
+        .serve_dioxus_application(ServeConfig::new().expect("Failed to build ServeConfig"), App);
+
+    let listener = tokio::net::TcpListener::bind(dioxus::cli_config::fullstack_address_or_localhost())
+        .await
+        .expect("Failed to bind address");
+
+    axum::serve(listener, router.into_make_service())
+        .await
+        .expect("Server failed");
+}
+
 #[component]
 fn App() -> Element {
     rsx! {
@@ -36,22 +78,49 @@ fn App() -> Element {
 #[component]
 fn Home(query: String) -> Element {
     let mut query_signal = use_signal(|| query.clone());
+    let mut loaded_books = use_signal(Vec::<Book>::new);
+    let mut next_cursor = use_signal(|| None::<String>);
 
     let books_resource = use_resource(move || {
         let query = query_signal();
         async move { load_books(query).await }
     });
 
+    use_effect(move || {
+        // クエリが変わったら読み込み済みのページ一覧をリセットする
+        query_signal();
+        loaded_books.set(Vec::new());
+        next_cursor.set(None);
+    });
+
     let on_query_change = move |new_query: String| {
         query_signal.set(new_query);
     };
 
+    let on_load_more = move |_| async move {
+        match load_books_page(20, next_cursor(), SortOrder::TitleAsc, None).await {
+            Ok(page) => {
+                loaded_books.write().extend(page.books);
+                next_cursor.set(page.next_cursor);
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    };
+
     let content = match books_resource.read().as_ref() {
-        Some(Ok(books)) => {
+        Some(Ok(books)) if !query_signal().is_empty() => {
             rsx! {
                 Books { books: books.clone() }
             }
         }
+        Some(Ok(_)) => {
+            rsx! {
+                Books { books: loaded_books() }
+                if next_cursor().is_some() || loaded_books().is_empty() {
+                    button { class: "border-1 rounded px-4 py-2 self-start", onclick: on_load_more, "Load more" }
+                }
+            }
+        }
         Some(Err(e)) => {
             eprintln!("{e}");
             rsx! {
@@ -65,14 +134,17 @@ fn Home(query: String) -> Element {
 
     rsx! {
         div { class: "flex flex-col gap-10",
-            div { SearchInput { query: query.clone(), on_query_change } }
+            div { class: "flex items-center gap-4",
+                SearchInput { query: query.clone(), on_query_change }
+                Link { to: Route::NewBook {}, class: "border-1 rounded px-4 py-2", "Add book" }
+            }
             div { class: "flex flex-col", {content} }
         }
     }
 }
 
 #[component]
-fn Books(books: Vec<Book>) -> Element {
+pub(crate) fn Books(books: Vec<Book>) -> Element {
     if books.is_empty() {
         rsx! { "No books to show" }
     } else {
@@ -86,6 +158,11 @@ fn Books(books: Vec<Book>) -> Element {
                         p { class: "text-gray-600 text-sm mt-1", {book.publisher} }
                         p { class: "text-green-600 font-semibold mt-2", "¥{book.price}" }
                         p { class: "text-xs text-gray-400 mt-1", "ISBN: {book.isbn}" }
+                        Link {
+                            to: Route::EditBook { id: book.id },
+                            class: "text-blue-600 text-sm mt-2 inline-block",
+                            "Edit"
+                        }
                     }
                 }
             }
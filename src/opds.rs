@@ -0,0 +1,128 @@
+use axum::{
+    extract::Query,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::books::{load_books_page, BookPage, SortOrder};
+
+#[derive(Deserialize)]
+pub struct OpdsParams {
+    cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: u32,
+    #[serde(default, rename = "q")]
+    query: Option<String>,
+}
+
+fn default_limit() -> u32 {
+    20
+}
+
+// OPDS 1.2 の取得(acquisition)フィードとして蔵書一覧を返す。`q`が指定された場合は
+// HTML側と同じFTS5検索・ページングのクエリ層(load_books_page)で絞り込む
+pub async fn feed(Query(params): Query<OpdsParams>) -> Response {
+    match load_books_page(
+        params.limit,
+        params.cursor,
+        SortOrder::TitleAsc,
+        params.query.clone(),
+    )
+    .await
+    {
+        Ok(page) => (
+            [(
+                header::CONTENT_TYPE,
+                "application/atom+xml;profile=opds-catalog;charset=utf-8",
+            )],
+            render_feed(&page, params.query.as_deref()),
+        )
+            .into_response(),
+        Err(e) => {
+            eprintln!("{e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load books").into_response()
+        }
+    }
+}
+
+fn render_feed(page: &BookPage, query: Option<&str>) -> String {
+    let mut entries = String::new();
+    for book in &page.books {
+        entries.push_str(&format!(
+            r#"  <entry>
+    <title>{title}</title>
+    <author><name>{author}</name></author>
+    <id>urn:isbn:{isbn}</id>
+    <dc:identifier>urn:isbn:{isbn}</dc:identifier>
+    <publisher>{publisher}</publisher>
+    <price>{price}</price>
+    <link rel="http://opds-spec.org/acquisition" type="application/atom+xml;profile=opds-catalog" href="/opds?cursor={id}"/>
+  </entry>
+"#,
+            title = escape_xml(&book.title),
+            author = escape_xml(&book.author),
+            isbn = escape_xml(&book.isbn),
+            publisher = escape_xml(&book.publisher),
+            price = book.price,
+            id = book.id,
+        ));
+    }
+
+    let query_suffix = query
+        .map(|q| format!("&q={}", percent_encode(q)))
+        .unwrap_or_default();
+
+    let self_href = match query {
+        Some(q) => format!("/opds?q={}", percent_encode(q)),
+        None => "/opds".to_string(),
+    };
+    let self_link = format!(
+        "  <link rel=\"self\" type=\"application/atom+xml;profile=opds-catalog\" href=\"{self_href}\"/>\n",
+    );
+
+    let next_link = page
+        .next_cursor
+        .as_ref()
+        .map(|cursor| {
+            format!(
+                "  <link rel=\"next\" type=\"application/atom+xml;profile=opds-catalog\" href=\"/opds?cursor={}{query_suffix}\"/>\n",
+                percent_encode(cursor)
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/terms/" xmlns:opds="http://opds-spec.org/2010/catalog">
+  <id>urn:academic-library:opds</id>
+  <title>academic-library</title>
+{self_link}  <link rel="start" type="application/atom+xml;profile=opds-catalog" href="/opds"/>
+{next_link}{entries}</feed>
+"#
+    )
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// href属性に埋め込む値をRFC3986準拠でパーセントエンコードする。
+// カーソルに含まれる制御文字(0x01)のようなXMLの本文として不正なバイトも、
+// ここを通すことで安全な文字だけのhrefに変換される
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
@@ -77,14 +77,122 @@ pub fn validate_book_form(
     } else if !is_valid_isbn_format(isbn_trimmed) {
         errors.isbn =
             Some("ISBNは10桁または13桁の数字で入力してください（ハイフンあり可）".to_string());
+    } else if !is_valid_isbn_checksum(isbn_trimmed) {
+        errors.isbn = Some("ISBNのチェックデジットが正しくありません".to_string());
     }
 
     errors
 }
 
 fn is_valid_isbn_format(isbn: &str) -> bool {
-    // ハイフンを除去して数字のみを抽出
-    let digits: String = isbn.chars().filter(|c| c.is_ascii_digit()).collect();
-    // ISBN-10（10桁）またはISBN-13（13桁）
-    digits.len() == 10 || digits.len() == 13
+    // ハイフンを除去し、残りがすべて数字であることを確認する（ISBN-10の末尾のみXを許容、ISBN-13にXは存在しない）
+    let stripped: String = isbn.chars().filter(|c| *c != '-').collect();
+    if stripped.len() != 10 && stripped.len() != 13 {
+        return false;
+    }
+    let is_isbn10 = stripped.len() == 10;
+    stripped.chars().enumerate().all(|(i, c)| {
+        c.is_ascii_digit() || (is_isbn10 && i == stripped.len() - 1 && c.to_ascii_uppercase() == 'X')
+    })
+}
+
+// ISBN-10/ISBN-13のチェックデジットを検証する
+fn is_valid_isbn_checksum(isbn: &str) -> bool {
+    let stripped: String = isbn.chars().filter(|c| *c != '-').collect();
+
+    if stripped.len() == 10 {
+        let sum: u32 = stripped
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let value = if c.to_ascii_uppercase() == 'X' {
+                    10
+                } else {
+                    c.to_digit(10).unwrap_or(0)
+                };
+                value * (10 - i as u32)
+            })
+            .sum();
+        sum % 11 == 0
+    } else if stripped.len() == 13 {
+        let sum: u32 = stripped
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let digit = c.to_digit(10).unwrap_or(0);
+                let weight = if i % 2 == 0 { 1 } else { 3 };
+                digit * weight
+            })
+            .sum();
+        sum % 10 == 0
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_isbn10() {
+        assert!(is_valid_isbn_format("0-306-40615-2"));
+        assert!(is_valid_isbn_checksum("0-306-40615-2"));
+    }
+
+    #[test]
+    fn accepts_valid_isbn10_with_x_check_digit() {
+        assert!(is_valid_isbn_format("156881111X"));
+        assert!(is_valid_isbn_checksum("156881111X"));
+    }
+
+    #[test]
+    fn rejects_isbn10_with_transposed_digits() {
+        // 末尾2桁を入れ替えただけの、桁数もフォーマットも正しいが誤ったISBN
+        assert!(is_valid_isbn_format("0306406125"));
+        assert!(!is_valid_isbn_checksum("0306406125"));
+    }
+
+    #[test]
+    fn accepts_valid_isbn13() {
+        assert!(is_valid_isbn_format("978-0-306-40615-7"));
+        assert!(is_valid_isbn_checksum("978-0-306-40615-7"));
+    }
+
+    #[test]
+    fn rejects_isbn13_with_wrong_check_digit() {
+        assert!(is_valid_isbn_format("978-0-306-40615-8"));
+        assert!(!is_valid_isbn_checksum("978-0-306-40615-8"));
+    }
+
+    #[test]
+    fn rejects_isbn13_with_trailing_x() {
+        // ISBN-13にチェックデジットXは存在しない（ISBN-10専用）
+        assert!(!is_valid_isbn_format("978030640614X"));
+    }
+
+    #[test]
+    fn rejects_non_digit_characters() {
+        assert!(!is_valid_isbn_format("abcd40615e"));
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        assert!(!is_valid_isbn_format("123456789"));
+    }
+
+    #[test]
+    fn validate_book_form_reports_checksum_error_distinct_from_format_error() {
+        let errors = validate_book_form("Title", "Author", "Publisher", "100", "0306406125");
+        assert_eq!(
+            errors.isbn.as_deref(),
+            Some("ISBNのチェックデジットが正しくありません")
+        );
+
+        let errors = validate_book_form("Title", "Author", "Publisher", "100", "123456789");
+        assert_eq!(
+            errors.isbn.as_deref(),
+            Some("ISBNは10桁または13桁の数字で入力してください（ハイフンあり可）")
+        );
+    }
 }